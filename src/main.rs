@@ -1,5 +1,62 @@
-use std::{fs::File, io::Read};
-use bytemuck::{Pod, Zeroable, NoUninit};
+use std::{fs::{File, OpenOptions}, io::{Cursor, Read, Seek, SeekFrom, Write}};
+use bytemuck::{Pod, Zeroable};
+
+trait DiskSource {
+    fn read_exact(&mut self, offset: u64, buf: &mut [u8]) -> Result<(), std::io::Error>;
+    fn write_exact(&mut self, offset: u64, buf: &[u8]) -> Result<(), std::io::Error>;
+    fn flush(&mut self) -> Result<(), std::io::Error>;
+}
+
+impl<T: Read + Write + Seek> DiskSource for T {
+    fn read_exact(&mut self, offset: u64, buf: &mut [u8]) -> Result<(), std::io::Error> {
+        self.seek(SeekFrom::Start(offset))?;
+        Read::read_exact(self, buf)
+    }
+
+    fn write_exact(&mut self, offset: u64, buf: &[u8]) -> Result<(), std::io::Error> {
+        self.seek(SeekFrom::Start(offset))?;
+        Write::write_all(self, buf)
+    }
+
+    fn flush(&mut self) -> Result<(), std::io::Error> {
+        Write::flush(self)
+    }
+}
+
+#[derive(Debug)]
+enum FatError {
+    Io(std::io::Error),
+    NotAFatFilesystem,
+    OutOfBounds { offset: u64, len: usize },
+    InvalidBootSector,
+    FileNotFound,
+    BrokenClusterChain { cluster: u32 },
+    DiskFull,
+    Unsupported(String),
+}
+
+impl std::fmt::Display for FatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FatError::Io(e) => write!(f, "io error: {}", e),
+            FatError::NotAFatFilesystem => write!(f, "not a FAT filesystem"),
+            FatError::OutOfBounds { offset, len } => write!(f, "read of {} bytes at offset {} is out of bounds", len, offset),
+            FatError::InvalidBootSector => write!(f, "invalid boot sector"),
+            FatError::FileNotFound => write!(f, "file not found"),
+            FatError::BrokenClusterChain { cluster } => write!(f, "broken cluster chain at cluster {}", cluster),
+            FatError::DiskFull => write!(f, "not enough free space on disk"),
+            FatError::Unsupported(reason) => write!(f, "unsupported operation: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for FatError {}
+
+impl From<std::io::Error> for FatError {
+    fn from(e: std::io::Error) -> Self {
+        FatError::Io(e)
+    }
+}
 
 #[repr(C, packed)]
 #[derive(Debug, Copy, Clone)]
@@ -50,12 +107,211 @@ struct FATDirectoryEntry {
 unsafe impl Zeroable for FATDirectoryEntry {}
 unsafe impl Pod for FATDirectoryEntry {}
 
-trait FATPrepare {
-    fn load_image(path: &str) -> Result<Vec<u8>, String>;
-    fn read_bootsector(data: &Vec<u8>) -> Result<FATBootsector, String>;
-    fn read_root_directory(disk: &Vec<u8>, header: &FATBootsector) -> Result<(Vec<FATDirectoryEntry>, usize), String>;
-    fn read_fat(header: &FATBootsector, disk: &Vec<u8>) -> Result<Vec<u8>, String>;
-    fn read_sector<T>(header: &FATBootsector, disk: &Vec<u8>, lba: u32, total: u32) -> Result<Vec<T>, String> 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct DateTime {
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+}
+
+fn decode_dos_date(date: u16) -> (u16, u8, u8) {
+    let day = (date & 0x1F) as u8;
+    let month = ((date >> 5) & 0x0F) as u8;
+    let year = 1980 + ((date >> 9) & 0x7F);
+    (year, month, day)
+}
+
+fn decode_dos_time(time: u16) -> (u8, u8, u8) {
+    let second = ((time & 0x1F) * 2) as u8;
+    let minute = ((time >> 5) & 0x3F) as u8;
+    let hour = ((time >> 11) & 0x1F) as u8;
+    (hour, minute, second)
+}
+
+impl FATDirectoryEntry {
+    pub fn created(&self) -> DateTime {
+        let (year, month, day) = decode_dos_date(self.created_date);
+        let (hour, minute, second) = decode_dos_time(self.created_tile);
+        let tenths = self.created_time_tenths;
+        DateTime { year, month, day, hour, minute, second: second + tenths / 100 }
+    }
+
+    pub fn modified(&self) -> DateTime {
+        let (year, month, day) = decode_dos_date(self.last_modification_date);
+        let (hour, minute, second) = decode_dos_time(self.last_modification_time);
+        DateTime { year, month, day, hour, minute, second }
+    }
+
+    pub fn accessed(&self) -> DateTime {
+        let (year, month, day) = decode_dos_date(self.last_accessed_data);
+        DateTime { year, month, day, hour: 0, minute: 0, second: 0 }
+    }
+}
+
+const ATTR_LFN: u8 = 0x0F;
+const LFN_LAST_ENTRY: u8 = 0x40;
+const LFN_SEQ_MASK: u8 = 0x1F;
+
+#[repr(C, packed)]
+#[derive(Debug, Copy, Clone)]
+struct FATLongDirectoryEntry {
+    sequence: u8,
+    name1: [u16; 5],
+    attributes: u8,
+    entry_type: u8,
+    checksum: u8,
+    name2: [u16; 6],
+    first_cluster: u16,
+    name3: [u16; 2],
+}
+
+unsafe impl Zeroable for FATLongDirectoryEntry {}
+unsafe impl Pod for FATLongDirectoryEntry {}
+
+impl FATStruct for FATLongDirectoryEntry {
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() >= std::mem::size_of::<Self>() {
+            Some(*bytemuck::from_bytes(bytes))
+        } else {
+            None
+        }
+    }
+}
+
+fn lfn_checksum(name: &[u8; 11]) -> u8 {
+    let mut sum: u8 = 0;
+    for &byte in name.iter() {
+        sum = (((sum & 1) << 7) | (sum >> 1)).wrapping_add(byte);
+    }
+    sum
+}
+
+fn lfn_units_to_string(units: &[u16]) -> (String, bool) {
+    let mut out = String::new();
+    for &unit in units {
+        match unit {
+            0x0000 => return (out, true),
+            0xFFFF => return (out, false),
+            _ => {}
+        }
+        if let Some(c) = char::from_u32(unit as u32) {
+            out.push(c);
+        }
+    }
+    (out, false)
+}
+
+const ATTR_DIRECTORY: u8 = 0x10;
+const DIR_ENTRY_END: u8 = 0x00;
+const DIR_ENTRY_FREE: u8 = 0xE5;
+
+fn short_name_matches(raw_name: &[u8; 11], name_lower: &str) -> bool {
+    let base = String::from_utf8_lossy(&raw_name[0..8]).trim_end().to_string();
+    let ext = String::from_utf8_lossy(&raw_name[8..11]).trim_end().to_string();
+    let short = if ext.is_empty() { base } else { format!("{}.{}", base, ext) };
+    short.to_lowercase() == name_lower
+}
+
+fn lfn_group_matches(lfn_parts: &[FATLongDirectoryEntry], short: &FATDirectoryEntry, name_lower: &str) -> bool {
+    if lfn_parts.iter().any(|p| p.checksum != lfn_checksum(&short.name)) {
+        return false;
+    }
+
+    let mut ordered = lfn_parts.to_vec();
+    ordered.sort_by_key(|p| p.sequence & LFN_SEQ_MASK);
+
+    let Some(last) = ordered.last() else { return false };
+    let expected_count = (last.sequence & LFN_SEQ_MASK) as usize;
+    if last.sequence & LFN_LAST_ENTRY == 0 || expected_count != ordered.len() {
+        return false;
+    }
+
+    let mut long_name = String::new();
+    for part in ordered {
+        let name1 = part.name1;
+        let name2 = part.name2;
+        let name3 = part.name3;
+        let (chunk1, terminated1) = lfn_units_to_string(&name1);
+        long_name.push_str(&chunk1);
+        if terminated1 { break }
+        let (chunk2, terminated2) = lfn_units_to_string(&name2);
+        long_name.push_str(&chunk2);
+        if terminated2 { break }
+        let (chunk3, terminated3) = lfn_units_to_string(&name3);
+        long_name.push_str(&chunk3);
+        if terminated3 { break }
+    }
+
+    long_name.to_lowercase() == name_lower
+}
+
+fn find_entry<'a>(entries: &'a [FATDirectoryEntry], name: &str) -> Option<&'a FATDirectoryEntry> {
+    let name_lower = name.to_lowercase();
+    let mut lfn_parts: Vec<FATLongDirectoryEntry> = vec![];
+    for entry in entries {
+        match entry.name[0] {
+            DIR_ENTRY_END => break,
+            DIR_ENTRY_FREE => { lfn_parts.clear(); continue }
+            _ => {}
+        }
+
+        if entry.attributes == ATTR_LFN {
+            if let Some(lfn) = FATLongDirectoryEntry::from_bytes(bytemuck::bytes_of(entry)) {
+                lfn_parts.push(lfn);
+            }
+            continue;
+        }
+
+        if !lfn_parts.is_empty() {
+            if lfn_group_matches(&lfn_parts, entry, &name_lower) {
+                return Some(entry);
+            }
+            lfn_parts.clear();
+        }
+
+        if short_name_matches(&entry.name, &name_lower) {
+            return Some(entry);
+        }
+    }
+    None
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum FatType {
+    Fat12,
+    Fat16,
+    Fat32,
+}
+
+impl FatType {
+    fn from_cluster_count(total_clusters: u32) -> FatType {
+        if total_clusters < 4085 {
+            FatType::Fat12
+        } else if total_clusters < 65525 {
+            FatType::Fat16
+        } else {
+            FatType::Fat32
+        }
+    }
+
+    fn is_end_of_chain(self, cluster: u32) -> bool {
+        match self {
+            FatType::Fat12 => cluster >= 0x0FF8,
+            FatType::Fat16 => cluster >= 0xFFF8,
+            FatType::Fat32 => cluster >= 0x0FFF_FFF8,
+        }
+    }
+}
+
+trait FATPrepare<D: DiskSource> {
+    fn read_bootsector(disk: &mut D, offset: u64) -> Result<FATBootsector, FatError>;
+    fn determine_fat_type(disk: &mut D, offset: u64, header: &FATBootsector) -> Result<(FatType, u32), FatError>;
+    fn read_root_directory(disk: &mut D, offset: u64, header: &FATBootsector, fat_type: FatType, sector_per_fat: u32, fat: &[u8]) -> Result<(Vec<FATDirectoryEntry>, usize), FatError>;
+    fn read_fat(disk: &mut D, offset: u64, header: &FATBootsector, sector_per_fat: u32) -> Result<Vec<u8>, FatError>;
+    fn read_sector<T>(disk: &mut D, offset: u64, header: &FATBootsector, lba: u32, total: u32) -> Result<Vec<T>, FatError>
         where T: FATStruct;
 }
 
@@ -93,109 +349,375 @@ impl FATStruct for u8 {
     }
 }
 
-struct FAT12 {
-    disk: Vec<u8>,
+struct FAT12<D: DiskSource> {
+    disk: D,
+    offset: u64,
     bootsector: FATBootsector,
+    fat_type: FatType,
+    sector_per_fat: u32,
     rootdir: Vec<FATDirectoryEntry>,
     rootdir_end: usize,
     fat: Vec<u8>
 }
 
-impl FAT12 {
-    pub fn new(path: &str) -> Result<FAT12, String> {
-        let disk = Self::load_image(path)?;
-        let bootsector = Self::read_bootsector(&disk)?;        
-        let (rootdir, rootdir_end) = Self::read_root_directory(&disk, &bootsector)?;
-        let fat = Self::read_fat(&bootsector, &disk)?;
+impl FAT12<File> {
+    pub fn open(path: &str) -> Result<FAT12<File>, FatError> {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        Self::new(file)
+    }
+}
+
+impl FAT12<Cursor<Vec<u8>>> {
+    pub fn from_bytes(data: Vec<u8>) -> Result<FAT12<Cursor<Vec<u8>>>, FatError> {
+        Self::new(Cursor::new(data))
+    }
+}
+
+impl<D: DiskSource> FAT12<D> {
+    pub fn new(disk: D) -> Result<FAT12<D>, FatError> {
+        Self::with_offset(disk, 0)
+    }
+
+    pub fn with_offset(mut disk: D, offset: u64) -> Result<FAT12<D>, FatError> {
+        let bootsector = Self::read_bootsector(&mut disk, offset)?;
+        let (fat_type, sector_per_fat) = Self::determine_fat_type(&mut disk, offset, &bootsector)?;
+        let fat = Self::read_fat(&mut disk, offset, &bootsector, sector_per_fat)?;
+        let (rootdir, rootdir_end) = Self::read_root_directory(&mut disk, offset, &bootsector, fat_type, sector_per_fat, &fat)?;
         Ok(FAT12 {
             disk,
+            offset,
             bootsector,
+            fat_type,
+            sector_per_fat,
             rootdir,
             rootdir_end,
             fat
         })
-    }   
+    }
     
-    pub fn search_file(&mut self, name: &[u8]) -> Option<&FATDirectoryEntry> {
-        for i in 0..self.bootsector.root_dir_ent {
-            if self.rootdir[i as usize].name == name {
-                return Some(&self.rootdir[i as usize])
+    pub fn search_file(&mut self, name: &[u8]) -> Result<&FATDirectoryEntry, FatError> {
+        for i in 0..self.rootdir.len() {
+            if self.rootdir[i].name == name {
+                return Ok(&self.rootdir[i])
+            }
+        }
+        Err(FatError::FileNotFound)
+    }
+
+    pub fn search_file_by_name(&mut self, name: &str) -> Result<&FATDirectoryEntry, FatError> {
+        find_entry(&self.rootdir, name).ok_or(FatError::FileNotFound)
+    }
+
+    pub fn read_file(&mut self, entry: &FATDirectoryEntry) -> Result<Vec<u8>, FatError> {
+        let cluster = entry.low_16b_entry as u32 | ((entry.high_16b_entry as u32) << 16);
+        let mut data = Self::read_cluster_chain(&mut self.disk, self.offset, &self.bootsector, &self.fat, self.fat_type, self.rootdir_end, cluster)?;
+        data.truncate(entry.size as usize);
+        Ok(data)
+    }
+
+    pub fn read_directory(&mut self, entry: &FATDirectoryEntry) -> Result<Vec<FATDirectoryEntry>, FatError> {
+        if entry.attributes & ATTR_DIRECTORY == 0 {
+            return Err(FatError::Unsupported("entry is not a directory".to_string()))
+        }
+        let cluster = entry.low_16b_entry as u32 | ((entry.high_16b_entry as u32) << 16);
+        if cluster < 2 {
+            // A `..` entry one level below the root points at cluster 0, which
+            // means "the root directory" rather than an actual data cluster.
+            return Ok(self.rootdir.clone())
+        }
+        let bytes = Self::read_cluster_chain(&mut self.disk, self.offset, &self.bootsector, &self.fat, self.fat_type, self.rootdir_end, cluster)?;
+        let entries = bytes
+            .chunks_exact(std::mem::size_of::<FATDirectoryEntry>())
+            .filter_map(FATDirectoryEntry::from_bytes)
+            .collect::<Vec<FATDirectoryEntry>>();
+        Ok(entries)
+    }
+
+    pub fn open_path(&mut self, path: &str) -> Result<Vec<u8>, FatError> {
+        let components: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+        if components.is_empty() {
+            return Err(FatError::FileNotFound)
+        }
+
+        let mut current_dir = self.rootdir.clone();
+        for (i, component) in components.iter().enumerate() {
+            let entry = *find_entry(&current_dir, component).ok_or(FatError::FileNotFound)?;
+            let is_last = i == components.len() - 1;
+
+            if is_last {
+                if entry.attributes & ATTR_DIRECTORY != 0 {
+                    return Err(FatError::Unsupported("path refers to a directory, not a file".to_string()))
+                }
+                return self.read_file(&entry);
+            }
+
+            current_dir = self.read_directory(&entry)?;
+        }
+
+        Err(FatError::FileNotFound)
+    }
+
+    pub fn write_file(&mut self, name: &[u8], data: &[u8]) -> Result<(), FatError> {
+        if self.fat_type != FatType::Fat12 {
+            return Err(FatError::Unsupported("write_file only supports FAT12 images".to_string()))
+        }
+        if name.len() != 11 {
+            return Err(FatError::Unsupported("write_file name must be 11 bytes (8.3 format)".to_string()))
+        }
+
+        let mut name_arr = [0u8; 11];
+        name_arr.copy_from_slice(name);
+        if let Some(existing) = self.rootdir.iter().find(|e| e.name == name_arr) {
+            let old_cluster = existing.low_16b_entry as u32 | ((existing.high_16b_entry as u32) << 16);
+            if old_cluster >= 2 {
+                self.free_cluster_chain(old_cluster as u16);
             }
         }
-        None
+
+        let cluster_size = self.bootsector.bytes_per_sector as usize * self.bootsector.sector_per_cluster as usize;
+        let clusters_needed = data.len().div_ceil(cluster_size);
+        let clusters_needed = clusters_needed.max(1);
+
+        let clusters = self.allocate_clusters(clusters_needed)?;
+        for (i, &cluster) in clusters.iter().enumerate() {
+            let next = if i + 1 < clusters.len() { clusters[i + 1] } else { 0x0FFF };
+            Self::set_fat12_entry(&mut self.fat, cluster, next);
+        }
+
+        for (i, &cluster) in clusters.iter().enumerate() {
+            let start = i * cluster_size;
+            let end = ((i + 1) * cluster_size).min(data.len());
+            let mut buf = vec![0u8; cluster_size];
+            buf[..end - start].copy_from_slice(&data[start..end]);
+
+            let lba = self.rootdir_end + (cluster as usize - 2) * self.bootsector.sector_per_cluster as usize;
+            let pos = self.offset + lba as u64 * self.bootsector.bytes_per_sector as u64;
+            self.disk.write_exact(pos, &buf)?;
+        }
+
+        self.write_directory_entry(name, clusters[0], data.len() as u32)?;
+        self.flush_fat()
     }
 
-    pub fn read_file(&mut self, entry: &FATDirectoryEntry) -> Result<Vec<u8>, String> {
+    pub fn flush(&mut self) -> Result<(), FatError> {
+        self.disk.flush().map_err(FatError::from)
+    }
+
+    fn allocate_clusters(&self, count: usize) -> Result<Vec<u16>, FatError> {
+        let total_entries = (self.fat.len() * 2 / 3) as u16;
+        let mut free = vec![];
+        let mut cluster = 2u16;
+        while free.len() < count && cluster < total_entries {
+            if Self::get_fat12_entry(&self.fat, cluster) == 0x000 {
+                free.push(cluster);
+            }
+            cluster += 1;
+        }
+        if free.len() < count {
+            return Err(FatError::DiskFull)
+        }
+        Ok(free)
+    }
+
+    fn free_cluster_chain(&mut self, start_cluster: u16) {
+        let mut cluster = start_cluster;
+        loop {
+            let next = Self::get_fat12_entry(&self.fat, cluster);
+            Self::set_fat12_entry(&mut self.fat, cluster, 0x000);
+            if next < 2 || FatType::Fat12.is_end_of_chain(next as u32) {
+                break
+            }
+            cluster = next;
+        }
+    }
+
+    fn get_fat12_entry(fat: &[u8], cluster: u16) -> u16 {
+        let index = (cluster as usize * 3) / 2;
+        if cluster.is_multiple_of(2) {
+            ((fat[index] as u16) | ((fat[index + 1] as u16) << 8)) & 0x0FFF
+        } else {
+            ((fat[index] as u16) >> 4) | ((fat[index + 1] as u16) << 4)
+        }
+    }
+
+    fn set_fat12_entry(fat: &mut [u8], cluster: u16, value: u16) {
+        let index = (cluster as usize * 3) / 2;
+        if cluster.is_multiple_of(2) {
+            fat[index] = (value & 0xFF) as u8;
+            fat[index + 1] = (fat[index + 1] & 0xF0) | (((value >> 8) & 0x0F) as u8);
+        } else {
+            fat[index] = (fat[index] & 0x0F) | (((value & 0x0F) as u8) << 4);
+            fat[index + 1] = (value >> 4) as u8;
+        }
+    }
+
+    fn write_directory_entry(&mut self, name: &[u8], first_cluster: u16, size: u32) -> Result<(), FatError> {
+        let mut name_arr = [0u8; 11];
+        name_arr.copy_from_slice(name);
+
+        let index = match self.rootdir.iter().position(|e| e.name == name_arr) {
+            Some(i) => i,
+            None => self.rootdir.iter()
+                .position(|e| e.name[0] == DIR_ENTRY_END || e.name[0] == DIR_ENTRY_FREE)
+                .ok_or(FatError::DiskFull)?,
+        };
+
+        let mut entry = self.rootdir[index];
+        entry.name = name_arr;
+        entry.attributes = 0x20;
+        entry.low_16b_entry = first_cluster;
+        entry.high_16b_entry = 0;
+        entry.size = size;
+        self.rootdir[index] = entry;
+
+        let dir_start = self.offset + (self.bootsector.reserved_sectors as u64 + self.bootsector.fat_count as u64 * self.sector_per_fat as u64) * self.bootsector.bytes_per_sector as u64;
+        let pos = dir_start + index as u64 * std::mem::size_of::<FATDirectoryEntry>() as u64;
+        self.disk.write_exact(pos, bytemuck::bytes_of(&entry)).map_err(FatError::from)
+    }
+
+    fn flush_fat(&mut self) -> Result<(), FatError> {
+        let fat_start = self.offset + self.bootsector.reserved_sectors as u64 * self.bootsector.bytes_per_sector as u64;
+        let fat_len = self.sector_per_fat as u64 * self.bootsector.bytes_per_sector as u64;
+        for copy in 0..self.bootsector.fat_count as u64 {
+            let pos = fat_start + copy * fat_len;
+            self.disk.write_exact(pos, &self.fat)?;
+        }
+        Ok(())
+    }
+
+    fn read_cluster_chain(disk: &mut D, offset: u64, header: &FATBootsector, fat: &[u8], fat_type: FatType, data_start: usize, start_cluster: u32) -> Result<Vec<u8>, FatError> {
+        if start_cluster < 2 {
+            // Cluster 0/1 is never a valid data cluster: it's how a zero-length
+            // file's directory entry is encoded, so there's simply no data to read.
+            return Ok(vec![])
+        }
+
         let mut output = vec![];
-        let mut cluster = entry.low_16b_entry;
+        let mut cluster = start_cluster;
         loop {
-            let lba = self.rootdir_end + ((cluster - 2) * self.bootsector.sector_per_cluster as u16) as usize;
-            let data = Self::read_sector::<u8>(&self.bootsector, &self.disk, lba as u32, self.bootsector.sector_per_cluster as u32)?;
+            let lba = data_start + ((cluster - 2) as usize * header.sector_per_cluster as usize);
+            let data = Self::read_sector::<u8>(disk, offset, header, lba as u32, header.sector_per_cluster as u32)?;
             output.extend_from_slice(&data);
-            let fat_index = (cluster * 3 / 2) as usize;
-            if cluster % 2 == 0 {
-                cluster = ((self.fat[fat_index] as u16) | ((self.fat[fat_index + 1] as u16) << 8)) & 0x0FFF;
-            } else {
-                cluster = ((self.fat[fat_index] as u16) >> 4) | ((self.fat[fat_index + 1] as u16) << 4);
-            }            
-            if cluster > 0x0ff8 { break }
+            cluster = Self::next_cluster(fat, fat_type, cluster)?;
+            if fat_type.is_end_of_chain(cluster) { break }
         }
         Ok(output)
     }
 
-    pub fn parse(&mut self, file: &[u8]) -> Result<Vec<u8>, String> {
-        if let Some(&e) = self.search_file(file) {
-            let content = self.read_file(&e)?;            
-            return Ok(content);
-        } 
-        return Err("Error Parse File".to_string())
+    fn next_cluster(fat: &[u8], fat_type: FatType, cluster: u32) -> Result<u32, FatError> {
+        match fat_type {
+            FatType::Fat12 => {
+                let fat_index = (cluster * 3 / 2) as usize;
+                if fat_index + 1 >= fat.len() { return Err(FatError::BrokenClusterChain { cluster }) }
+                let value = if cluster.is_multiple_of(2) {
+                    ((fat[fat_index] as u32) | ((fat[fat_index + 1] as u32) << 8)) & 0x0FFF
+                } else {
+                    ((fat[fat_index] as u32) >> 4) | ((fat[fat_index + 1] as u32) << 4)
+                };
+                Ok(value)
+            }
+            FatType::Fat16 => {
+                let fat_index = (cluster * 2) as usize;
+                if fat_index + 1 >= fat.len() { return Err(FatError::BrokenClusterChain { cluster }) }
+                Ok((fat[fat_index] as u32) | ((fat[fat_index + 1] as u32) << 8))
+            }
+            FatType::Fat32 => {
+                let fat_index = (cluster * 4) as usize;
+                if fat_index + 3 >= fat.len() { return Err(FatError::BrokenClusterChain { cluster }) }
+                let value = (fat[fat_index] as u32)
+                    | ((fat[fat_index + 1] as u32) << 8)
+                    | ((fat[fat_index + 2] as u32) << 16)
+                    | ((fat[fat_index + 3] as u32) << 24);
+                Ok(value & 0x0FFF_FFFF)
+            }
+        }
+    }
+
+    pub fn parse(&mut self, file: &[u8]) -> Result<Vec<u8>, FatError> {
+        let entry = *self.search_file(file)?;
+        self.read_file(&entry)
     }
 }
 
-impl FATPrepare for FAT12 {
-    fn load_image(path: &str) -> Result<Vec<u8>, String> {    
-        let mut data = Vec::<u8>::new();
-        if let Ok(mut f) = File::open(path) {
-            f.read_to_end(&mut data)
-                .expect(format!("Cannot Read file at: {}", path).as_str());  
-        } else {
-            return Err("failed to load file".to_string());
+impl<D: DiskSource> FATPrepare<D> for FAT12<D> {
+    fn read_bootsector(disk: &mut D, offset: u64) -> Result<FATBootsector, FatError> {
+        let mut buf = [0u8; std::mem::size_of::<FATBootsector>()];
+        disk.read_exact(offset, &mut buf)?;
+        let header: FATBootsector = *bytemuck::from_bytes(&buf);
+        if header.bytes_per_sector == 0 || header.sector_per_cluster == 0 {
+            return Err(FatError::InvalidBootSector)
         }
-        Ok(data)
-    }    
-    
-    fn read_bootsector(data: &Vec<u8>) -> Result<FATBootsector, String> {
-        if data.len() < std::mem::size_of::<FATBootsector>() {
-            return Err("read_bootsector failed".to_string())
-        }
-        Ok(*bytemuck::from_bytes(&data[..std::mem::size_of::<FATBootsector>()]))
-    }    
+        Ok(header)
+    }
 
-    fn read_root_directory(disk: &Vec<u8>, header: &FATBootsector) -> Result<(Vec<FATDirectoryEntry>, usize), String> {
-        let lba = header.reserved_sectors + header.sector_per_fat * header.fat_count as u16;
-        let size = std::mem::size_of::<FATDirectoryEntry>() as u16 * header.root_dir_ent;
-        let sectors = size / header.bytes_per_sector;
-        let end = (lba + sectors) as usize;
-        let root = Self::read_sector::<FATDirectoryEntry>(header, disk, lba as u32, sectors as u32)?;
-        Ok((root, end))
+    fn determine_fat_type(disk: &mut D, offset: u64, header: &FATBootsector) -> Result<(FatType, u32), FatError> {
+        let sector_per_fat = if header.sector_per_fat != 0 {
+            header.sector_per_fat as u32
+        } else {
+            let mut buf = [0u8; 4];
+            disk.read_exact(offset + 36, &mut buf)?;
+            u32::from_le_bytes(buf)
+        };
+
+        let total_sectors = if header.total_sector != 0 {
+            header.total_sector as u32
+        } else {
+            header.large_sector_count
+        };
+
+        let root_dir_sectors = (header.root_dir_ent as u32 * std::mem::size_of::<FATDirectoryEntry>() as u32)
+            .div_ceil(header.bytes_per_sector as u32);
+
+        let data_sectors = total_sectors
+            .saturating_sub(header.reserved_sectors as u32 + header.fat_count as u32 * sector_per_fat + root_dir_sectors);
+        let total_clusters = data_sectors / header.sector_per_cluster as u32;
+
+        Ok((FatType::from_cluster_count(total_clusters), sector_per_fat))
+    }
+
+    fn read_root_directory(disk: &mut D, offset: u64, header: &FATBootsector, fat_type: FatType, sector_per_fat: u32, fat: &[u8]) -> Result<(Vec<FATDirectoryEntry>, usize), FatError> {
+        let data_start = header.reserved_sectors as u32 + header.fat_count as u32 * sector_per_fat;
+
+        match fat_type {
+            FatType::Fat12 | FatType::Fat16 => {
+                let size = std::mem::size_of::<FATDirectoryEntry>() as u32 * header.root_dir_ent as u32;
+                let sectors = size / header.bytes_per_sector as u32;
+                let end = (data_start + sectors) as usize;
+                let root = Self::read_sector::<FATDirectoryEntry>(disk, offset, header, data_start, sectors)?;
+                Ok((root, end))
+            }
+            FatType::Fat32 => {
+                let mut buf = [0u8; 4];
+                disk.read_exact(offset + 44, &mut buf)?;
+                let root_cluster = u32::from_le_bytes(buf);
+                let bytes = Self::read_cluster_chain(disk, offset, header, fat, fat_type, data_start as usize, root_cluster)?;
+                let entries = bytes
+                    .chunks_exact(std::mem::size_of::<FATDirectoryEntry>())
+                    .filter_map(FATDirectoryEntry::from_bytes)
+                    .collect::<Vec<FATDirectoryEntry>>();
+                Ok((entries, data_start as usize))
+            }
+        }
     }
 
-    fn read_fat(header: &FATBootsector, disk: &Vec<u8>) -> Result<Vec<u8>, String> {
-        let fat = Self::read_sector::<u8>(header, disk, header.reserved_sectors as u32, header.sector_per_fat as u32)?;
+    fn read_fat(disk: &mut D, offset: u64, header: &FATBootsector, sector_per_fat: u32) -> Result<Vec<u8>, FatError> {
+        if sector_per_fat == 0 {
+            return Err(FatError::NotAFatFilesystem)
+        }
+        let fat = Self::read_sector::<u8>(disk, offset, header, header.reserved_sectors as u32, sector_per_fat)?;
         Ok(fat)
     }
 
-    fn read_sector<T>(header: &FATBootsector, disk: &Vec<u8>, lba: u32, total: u32) -> Result<Vec<T>, String> 
+    fn read_sector<T>(disk: &mut D, offset: u64, header: &FATBootsector, lba: u32, total: u32) -> Result<Vec<T>, FatError>
         where T: FATStruct
     {
-        let start_pos = (lba * header.bytes_per_sector as u32) as usize;
-        let end_pos = (start_pos + total as usize * header.bytes_per_sector as usize) as usize;
-        
-        if end_pos > disk.len() {
-            return Err("read_sector out of bound".to_string())
-        }
+        let start_pos = offset + (lba as u64 * header.bytes_per_sector as u64);
+        let size = total as u64 * header.bytes_per_sector as u64;
+
+        let mut sector_data = vec![0u8; size as usize];
+        disk.read_exact(start_pos, &mut sector_data)
+            .map_err(|_| FatError::OutOfBounds { offset: start_pos, len: size as usize })?;
 
-        let sector_data = &disk[start_pos..end_pos];
         let entry_size = std::mem::size_of::<T>();
         let entries = sector_data
             .chunks_exact(entry_size)
@@ -206,7 +728,571 @@ impl FATPrepare for FAT12 {
     }
 }
 
-fn main() {
-    let mut fat12 = FAT12::new("os.img").expect("err create Fat12");
-    println!("{}", String::from_utf8(fat12.parse(b"TEST    TXT").expect("parse error")).expect("msg"));
+fn main() -> Result<(), FatError> {
+    let mut fat12 = FAT12::open("os.img")?;
+
+    for entry in fat12.rootdir.clone() {
+        if entry.name[0] == DIR_ENTRY_END {
+            break;
+        }
+        if entry.name[0] == DIR_ENTRY_FREE || entry.attributes == ATTR_LFN {
+            continue;
+        }
+
+        let created = entry.created();
+        let modified = entry.modified();
+        let accessed = entry.accessed();
+        println!(
+            "{}  created {:?}  modified {:?}  accessed {:?}",
+            String::from_utf8_lossy(&entry.name), created, modified, accessed
+        );
+
+        if entry.attributes & ATTR_DIRECTORY != 0 {
+            let children = fat12.read_directory(&entry)?;
+            println!("  -> {} entries", children.len());
+        }
+    }
+
+    if let Ok(data) = fat12.parse(b"TEST    TXT") {
+        println!("TEST.TXT via parse(): {}", String::from_utf8_lossy(&data));
+    }
+
+    if let Ok(entry) = fat12.search_file_by_name("test.txt") {
+        let entry = *entry;
+        let data = fat12.read_file(&entry)?;
+        println!("test.txt: {}", String::from_utf8_lossy(&data));
+    }
+
+    if let Ok(data) = fat12.open_path("SUB/FILE.TXT") {
+        println!("SUB/FILE.TXT: {}", String::from_utf8_lossy(&data));
+    }
+
+    fat12.write_file(b"LOG     TXT", b"rfat12 ran\n")?;
+    fat12.flush()?;
+
+    // Also demonstrate mounting an already-loaded image (e.g. fetched over the
+    // network) via the in-memory constructor instead of a file path.
+    let raw = std::fs::read("os.img")?;
+    let mut embedded = FAT12::from_bytes(raw)?;
+    if let Ok(entry) = embedded.search_file_by_name("test.txt") {
+        let entry = *entry;
+        let data = embedded.read_file(&entry)?;
+        println!("test.txt via in-memory image: {}", String::from_utf8_lossy(&data));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pads `base`/`ext` into an 8.3 short name as stored on disk.
+    fn name83(base: &str, ext: &str) -> [u8; 11] {
+        let mut out = [b' '; 11];
+        out[..base.len()].copy_from_slice(base.as_bytes());
+        out[8..8 + ext.len()].copy_from_slice(ext.as_bytes());
+        out
+    }
+
+    fn short_entry(name: [u8; 11], attributes: u8, cluster: u16, size: u32) -> FATDirectoryEntry {
+        FATDirectoryEntry {
+            name,
+            attributes,
+            reserved: 0,
+            created_time_tenths: 0,
+            created_tile: 0,
+            created_date: 0,
+            last_accessed_data: 0,
+            high_16b_entry: 0,
+            last_modification_time: 0,
+            last_modification_date: 0,
+            low_16b_entry: cluster,
+            size,
+        }
+    }
+
+    fn lfn_entry(sequence: u8, chars: &str, checksum: u8) -> FATLongDirectoryEntry {
+        let mut units = [0xFFFFu16; 13];
+        for (i, c) in chars.chars().enumerate() {
+            units[i] = c as u16;
+        }
+        if chars.len() < 13 {
+            units[chars.len()] = 0x0000;
+        }
+        FATLongDirectoryEntry {
+            sequence,
+            name1: [units[0], units[1], units[2], units[3], units[4]],
+            attributes: ATTR_LFN,
+            entry_type: 0,
+            checksum,
+            name2: [units[5], units[6], units[7], units[8], units[9], units[10]],
+            first_cluster: 0,
+            name3: [units[11], units[12]],
+        }
+    }
+
+    /// Reinterprets an LFN record as a directory entry, mirroring how `find_entry`
+    /// encounters it while scanning a directory region.
+    fn as_dir_entry(lfn: &FATLongDirectoryEntry) -> FATDirectoryEntry {
+        FATDirectoryEntry::from_bytes(bytemuck::bytes_of(lfn)).unwrap()
+    }
+
+    #[test]
+    fn lfn_checksum_matches_reference_algorithm() {
+        let name = name83("TEST", "TXT");
+        let mut sum: u8 = 0;
+        for &byte in name.iter() {
+            sum = (((sum & 1) << 7) | (sum >> 1)).wrapping_add(byte);
+        }
+        assert_eq!(lfn_checksum(&name), sum);
+    }
+
+    #[test]
+    fn find_entry_resolves_long_name_case_insensitively() {
+        let short_name = name83("TEST~1", "TXT");
+        let short = short_entry(short_name, 0x20, 2, 4);
+        let checksum = lfn_checksum(&short_name);
+        let lfn = lfn_entry(1 | LFN_LAST_ENTRY, "long file.txt", checksum);
+
+        let entries = vec![as_dir_entry(&lfn), short];
+        let found = find_entry(&entries, "LONG FILE.TXT").expect("should find by long name");
+        let cluster = found.low_16b_entry;
+        assert_eq!(cluster, 2);
+    }
+
+    #[test]
+    fn find_entry_rejects_lfn_group_missing_last_entry_marker() {
+        let short_name = name83("TEST~1", "TXT");
+        let short = short_entry(short_name, 0x20, 2, 4);
+        let checksum = lfn_checksum(&short_name);
+        // Sequence 1 without the 0x40 marker: a truncated run missing its leading entry.
+        let lfn = lfn_entry(1, "long file.txt", checksum);
+
+        let entries = vec![as_dir_entry(&lfn), short];
+        assert!(find_entry(&entries, "LONG FILE.TXT").is_none());
+        assert!(find_entry(&entries, "TEST~1.TXT").is_some());
+    }
+
+    #[test]
+    fn fat_type_from_cluster_count_picks_the_right_variant() {
+        assert_eq!(FatType::from_cluster_count(0), FatType::Fat12);
+        assert_eq!(FatType::from_cluster_count(4084), FatType::Fat12);
+        assert_eq!(FatType::from_cluster_count(4085), FatType::Fat16);
+        assert_eq!(FatType::from_cluster_count(65524), FatType::Fat16);
+        assert_eq!(FatType::from_cluster_count(65525), FatType::Fat32);
+    }
+
+    #[test]
+    fn fat_type_end_of_chain_thresholds_match_the_spec() {
+        assert!(!FatType::Fat12.is_end_of_chain(0x0FF7));
+        assert!(FatType::Fat12.is_end_of_chain(0x0FF8));
+        assert!(!FatType::Fat16.is_end_of_chain(0xFFF7));
+        assert!(FatType::Fat16.is_end_of_chain(0xFFF8));
+        assert!(!FatType::Fat32.is_end_of_chain(0x0FFF_FFF7));
+        assert!(FatType::Fat32.is_end_of_chain(0x0FFF_FFF8));
+    }
+
+    #[test]
+    fn read_file_on_a_zero_length_entry_returns_empty_without_underflowing() {
+        // A zero-length file's directory entry stores cluster 0, which is not a
+        // valid data cluster; `cluster - 2` must not be computed against it.
+        let empty_entry = short_entry(name83("EMPTY", "TXT"), 0x20, 0, 0);
+        let image = build_fat12_image(&[empty_entry], &[], &[]);
+
+        let mut fat12 = FAT12::from_bytes(image).expect("mount image");
+        let entry = *fat12.search_file_by_name("EMPTY.TXT").expect("find empty file");
+        let data = fat12.read_file(&entry).expect("read empty file without panicking");
+        assert_eq!(data, Vec::<u8>::new());
+    }
+
+    #[test]
+    fn disk_source_blanket_impl_reads_and_writes_through_cursor() {
+        let mut cursor = Cursor::new(vec![0u8; 16]);
+        DiskSource::write_exact(&mut cursor, 4, &[1, 2, 3, 4]).unwrap();
+        let mut buf = [0u8; 4];
+        DiskSource::read_exact(&mut cursor, 4, &mut buf).unwrap();
+        assert_eq!(buf, [1, 2, 3, 4]);
+    }
+
+    fn make_bootsector(bytes_per_sector: u16, sector_per_cluster: u8, reserved_sectors: u16, fat_count: u8, root_dir_ent: u16, total_sector: u16, sector_per_fat: u16) -> FATBootsector {
+        FATBootsector {
+            jmp_inst: [0xEB, 0x3C, 0x90],
+            oem: *b"TESTOS  ",
+            bytes_per_sector,
+            sector_per_cluster,
+            reserved_sectors,
+            fat_count,
+            root_dir_ent,
+            total_sector,
+            media_descriptor_type: 0xF8,
+            sector_per_fat,
+            sector_per_track: 18,
+            heads: 2,
+            hidden_sector: 0,
+            large_sector_count: 0,
+            drive_number: 0x80,
+            reserved: 0,
+            signature: 0x29,
+            volume_serial: 0x1234_5678,
+            volume_label: *b"NO NAME    ",
+            system_identifier: *b"FAT12   ",
+        }
+    }
+
+    fn pack_fat12_entry(fat: &mut [u8], cluster: u16, value: u16) {
+        let index = (cluster as usize * 3) / 2;
+        if cluster % 2 == 0 {
+            fat[index] = (value & 0xFF) as u8;
+            fat[index + 1] = (fat[index + 1] & 0xF0) | (((value >> 8) & 0x0F) as u8);
+        } else {
+            fat[index] = (fat[index] & 0x0F) | (((value & 0x0F) as u8) << 4);
+            fat[index + 1] = (value >> 4) as u8;
+        }
+    }
+
+    /// Builds a minimal in-memory FAT12 image: one boot sector, two FAT copies,
+    /// a one-sector root directory, and a 20-sector data region.
+    fn build_fat12_image(root_entries: &[FATDirectoryEntry], fat_links: &[(u16, u16)], clusters_data: &[(u32, Vec<u8>)]) -> Vec<u8> {
+        let bytes_per_sector = 512u16;
+        let sector_per_cluster = 1u8;
+        let reserved_sectors = 1u16;
+        let fat_count = 2u8;
+        let root_dir_ent = 16u16;
+        let sector_per_fat = 1u16;
+        let data_sectors = 20u16;
+        let root_dir_sectors = root_dir_ent * 32 / bytes_per_sector;
+        let total_sector = reserved_sectors + fat_count as u16 * sector_per_fat + root_dir_sectors + data_sectors;
+
+        let boot = make_bootsector(bytes_per_sector, sector_per_cluster, reserved_sectors, fat_count, root_dir_ent, total_sector, sector_per_fat);
+        let mut image = vec![0u8; total_sector as usize * bytes_per_sector as usize];
+        image[..std::mem::size_of::<FATBootsector>()].copy_from_slice(bytemuck::bytes_of(&boot));
+
+        let fat_region_start = reserved_sectors as usize * bytes_per_sector as usize;
+        let fat_len = sector_per_fat as usize * bytes_per_sector as usize;
+        let mut fat_bytes = vec![0u8; fat_len];
+        for &(cluster, value) in fat_links {
+            pack_fat12_entry(&mut fat_bytes, cluster, value);
+        }
+        for copy in 0..fat_count as usize {
+            let off = fat_region_start + copy * fat_len;
+            image[off..off + fat_len].copy_from_slice(&fat_bytes);
+        }
+
+        let root_dir_start = fat_region_start + fat_count as usize * fat_len;
+        for (i, entry) in root_entries.iter().enumerate() {
+            let off = root_dir_start + i * 32;
+            image[off..off + 32].copy_from_slice(bytemuck::bytes_of(entry));
+        }
+
+        let data_start = root_dir_start + root_dir_ent as usize * 32;
+        for (cluster, bytes) in clusters_data {
+            let off = data_start + (*cluster as usize - 2) * sector_per_cluster as usize * bytes_per_sector as usize;
+            image[off..off + bytes.len()].copy_from_slice(bytes);
+        }
+
+        image
+    }
+
+    fn pack_fat16_entry(fat: &mut [u8], cluster: u16, value: u16) {
+        let index = cluster as usize * 2;
+        fat[index..index + 2].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn pack_fat32_entry(fat: &mut [u8], cluster: u32, value: u32) {
+        let index = cluster as usize * 4;
+        fat[index..index + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    /// Builds a minimal in-memory FAT16 image, structured exactly like
+    /// `build_fat12_image` (fixed-size root directory region, 20-sector data
+    /// region) except its on-disk `total_sector` is inflated to report a cluster
+    /// count inside the FAT16 range, since `determine_fat_type` classifies a
+    /// volume purely from that arithmetic rather than from its actual size.
+    fn build_fat16_image(root_entries: &[FATDirectoryEntry], fat_links: &[(u16, u16)], clusters_data: &[(u32, Vec<u8>)]) -> Vec<u8> {
+        let bytes_per_sector = 512u16;
+        let sector_per_cluster = 1u8;
+        let reserved_sectors = 1u16;
+        let fat_count = 2u8;
+        let root_dir_ent = 16u16;
+        let sector_per_fat = 1u16;
+        let data_sectors = 20u16;
+        let root_dir_sectors = root_dir_ent * 32 / bytes_per_sector;
+        let real_total_sector = reserved_sectors + fat_count as u16 * sector_per_fat + root_dir_sectors + data_sectors;
+        let overhead = reserved_sectors + fat_count as u16 * sector_per_fat + root_dir_sectors;
+        let reported_total_sector = overhead + 5000;
+
+        let boot = make_bootsector(bytes_per_sector, sector_per_cluster, reserved_sectors, fat_count, root_dir_ent, reported_total_sector, sector_per_fat);
+        let mut image = vec![0u8; real_total_sector as usize * bytes_per_sector as usize];
+        image[..std::mem::size_of::<FATBootsector>()].copy_from_slice(bytemuck::bytes_of(&boot));
+
+        let fat_region_start = reserved_sectors as usize * bytes_per_sector as usize;
+        let fat_len = sector_per_fat as usize * bytes_per_sector as usize;
+        let mut fat_bytes = vec![0u8; fat_len];
+        for &(cluster, value) in fat_links {
+            pack_fat16_entry(&mut fat_bytes, cluster, value);
+        }
+        for copy in 0..fat_count as usize {
+            let off = fat_region_start + copy * fat_len;
+            image[off..off + fat_len].copy_from_slice(&fat_bytes);
+        }
+
+        let root_dir_start = fat_region_start + fat_count as usize * fat_len;
+        for (i, entry) in root_entries.iter().enumerate() {
+            let off = root_dir_start + i * 32;
+            image[off..off + 32].copy_from_slice(bytemuck::bytes_of(entry));
+        }
+
+        let data_start = root_dir_start + root_dir_ent as usize * 32;
+        for (cluster, bytes) in clusters_data {
+            let off = data_start + (*cluster as usize - 2) * sector_per_cluster as usize * bytes_per_sector as usize;
+            image[off..off + bytes.len()].copy_from_slice(bytes);
+        }
+
+        image
+    }
+
+    /// Builds a minimal in-memory FAT32 image. Unlike FAT12/16, FAT32 has no
+    /// fixed-size root directory region: `root_entries` are written into
+    /// `root_cluster`'s data cluster instead, and the boot sector's raw byte 44
+    /// (the layout `read_root_directory` actually reads, inside `volume_label`)
+    /// is poked directly with that cluster number. `total_sector` is left at 0,
+    /// so `large_sector_count` carries the inflated sector count (overhead plus
+    /// 70,000 clusters) that `determine_fat_type` classifies from instead.
+    fn build_fat32_image(root_entries: &[FATDirectoryEntry], root_cluster: u32, fat_links: &[(u32, u32)], clusters_data: &[(u32, Vec<u8>)]) -> Vec<u8> {
+        let bytes_per_sector = 512u16;
+        let sector_per_cluster = 1u8;
+        let reserved_sectors = 1u16;
+        let fat_count = 2u8;
+        let sector_per_fat = 1u16;
+        let data_sectors = 20u16;
+        let real_total_sector = reserved_sectors + fat_count as u16 * sector_per_fat + data_sectors;
+        let overhead = reserved_sectors as u32 + fat_count as u32 * sector_per_fat as u32;
+        let reported_total_clusters = 70_000u32;
+
+        let mut boot = make_bootsector(bytes_per_sector, sector_per_cluster, reserved_sectors, fat_count, 0, 0, sector_per_fat);
+        boot.large_sector_count = overhead + reported_total_clusters;
+        let mut boot_bytes = bytemuck::bytes_of(&boot).to_vec();
+        boot_bytes[44..48].copy_from_slice(&root_cluster.to_le_bytes());
+
+        let mut image = vec![0u8; real_total_sector as usize * bytes_per_sector as usize];
+        image[..boot_bytes.len()].copy_from_slice(&boot_bytes);
+
+        let fat_region_start = reserved_sectors as usize * bytes_per_sector as usize;
+        let fat_len = sector_per_fat as usize * bytes_per_sector as usize;
+        let mut fat_bytes = vec![0u8; fat_len];
+        for &(cluster, value) in fat_links {
+            pack_fat32_entry(&mut fat_bytes, cluster, value);
+        }
+        for copy in 0..fat_count as usize {
+            let off = fat_region_start + copy * fat_len;
+            image[off..off + fat_len].copy_from_slice(&fat_bytes);
+        }
+
+        let data_start = fat_region_start + fat_count as usize * fat_len;
+        let cluster_off = |cluster: u32| data_start + (cluster as usize - 2) * sector_per_cluster as usize * bytes_per_sector as usize;
+
+        let root_off = cluster_off(root_cluster);
+        for (i, entry) in root_entries.iter().enumerate() {
+            let off = root_off + i * 32;
+            image[off..off + 32].copy_from_slice(bytemuck::bytes_of(entry));
+        }
+
+        for (cluster, bytes) in clusters_data {
+            let off = cluster_off(*cluster);
+            image[off..off + bytes.len()].copy_from_slice(bytes);
+        }
+
+        image
+    }
+
+    #[test]
+    fn with_offset_mounts_a_filesystem_embedded_at_a_partition_boundary() {
+        let image = build_fat12_image(&[], &[], &[]);
+        let mut wrapped = vec![0u8; 512];
+        wrapped.extend_from_slice(&image);
+
+        let fat12 = FAT12::with_offset(Cursor::new(wrapped), 512).expect("mount at offset");
+        assert_eq!(fat12.fat_type, FatType::Fat12);
+    }
+
+    #[test]
+    fn read_file_walks_a_fat16_cluster_chain() {
+        let file_entry = short_entry(name83("FILE", "TXT"), 0x20, 2, 5);
+        let image = build_fat16_image(&[file_entry], &[(2, 0xFFFF)], &[(2, b"hello".to_vec())]);
+
+        let mut fat16 = FAT12::from_bytes(image).expect("mount image");
+        assert_eq!(fat16.fat_type, FatType::Fat16);
+        let entry = *fat16.search_file_by_name("FILE.TXT").expect("find file");
+        let data = fat16.read_file(&entry).expect("read file via FAT16 chain");
+        assert_eq!(data, b"hello");
+    }
+
+    #[test]
+    fn fat32_root_directory_is_read_as_a_cluster_chain() {
+        let file_entry = short_entry(name83("FILE", "TXT"), 0x20, 3, 5);
+        let image = build_fat32_image(
+            &[file_entry],
+            2,
+            &[(2, 0x0FFF_FFF8), (3, 0x0FFF_FFF8)],
+            &[(3, b"world".to_vec())],
+        );
+
+        let mut fat32 = FAT12::from_bytes(image).expect("mount image");
+        assert_eq!(fat32.fat_type, FatType::Fat32);
+        let entry = *fat32.search_file_by_name("FILE.TXT").expect("find file via FAT32 root chain");
+        let data = fat32.read_file(&entry).expect("read file via FAT32 chain");
+        assert_eq!(data, b"world");
+    }
+
+    #[test]
+    fn open_path_recurses_into_subdirectories() {
+        let file_entry = short_entry(name83("FILE", "TXT"), 0x20, 4, 5);
+        let mut sub_dir_bytes = vec![0u8; 512];
+        sub_dir_bytes[..32].copy_from_slice(bytemuck::bytes_of(&file_entry));
+
+        let sub_entry = short_entry(name83("SUB", ""), ATTR_DIRECTORY, 3, 0);
+        let image = build_fat12_image(
+            &[sub_entry],
+            &[(3, 0x0FFF), (4, 0x0FFF)],
+            &[(3, sub_dir_bytes), (4, b"hello".to_vec())],
+        );
+
+        let mut fat12 = FAT12::from_bytes(image).expect("mount image");
+        let data = fat12.open_path("SUB/FILE.TXT").expect("resolve nested path");
+        assert_eq!(data, b"hello");
+    }
+
+    #[test]
+    fn open_path_rejects_a_path_pointing_at_a_directory() {
+        let sub_entry = short_entry(name83("SUB", ""), ATTR_DIRECTORY, 3, 0);
+        let image = build_fat12_image(&[sub_entry], &[(3, 0x0FFF)], &[(3, vec![0u8; 512])]);
+
+        let mut fat12 = FAT12::from_bytes(image).expect("mount image");
+        assert!(matches!(fat12.open_path("SUB"), Err(FatError::Unsupported(_))));
+    }
+
+    #[test]
+    fn open_path_resolves_dot_dot_pointing_at_the_root_directory() {
+        // Per the FAT spec, a subdirectory's ".." entry points at cluster 0 when
+        // its parent is the root directory -- that must not underflow `cluster - 2`.
+        let dot_dot = short_entry(name83("..", ""), ATTR_DIRECTORY, 0, 0);
+        let mut sub_dir_bytes = vec![0u8; 512];
+        sub_dir_bytes[..32].copy_from_slice(bytemuck::bytes_of(&dot_dot));
+
+        let sub_entry = short_entry(name83("SUB", ""), ATTR_DIRECTORY, 3, 0);
+        let file_entry = short_entry(name83("FILE", "TXT"), 0x20, 5, 5);
+        let image = build_fat12_image(
+            &[sub_entry, file_entry],
+            &[(3, 0x0FFF), (5, 0x0FFF)],
+            &[(3, sub_dir_bytes), (5, b"world".to_vec())],
+        );
+
+        let mut fat12 = FAT12::from_bytes(image).expect("mount image");
+        let data = fat12.open_path("SUB/../FILE.TXT").expect("resolve path through .. to root");
+        assert_eq!(data, b"world");
+    }
+
+    #[test]
+    fn write_file_allocates_a_cluster_chain_written_to_both_fat_copies() {
+        let image = build_fat12_image(&[], &[], &[]);
+        let mut fat12 = FAT12::from_bytes(image).expect("mount image");
+        fat12.write_file(&name83("HELLO", "TXT"), b"hi there").expect("write file");
+
+        let entry = *fat12.search_file_by_name("HELLO.TXT").expect("find written file");
+        let data = fat12.read_file(&entry).expect("read back file");
+        assert_eq!(data, b"hi there");
+
+        // Both on-disk FAT copies (one sector each, starting right after the boot sector)
+        // must have been flushed identically.
+        let disk = fat12.disk.get_ref();
+        let fat_len = 512;
+        let first_copy = &disk[512..512 + fat_len];
+        let second_copy = &disk[512 + fat_len..512 + 2 * fat_len];
+        assert_eq!(first_copy, second_copy, "both FAT copies should match after a write");
+    }
+
+    #[test]
+    fn write_file_overwrite_frees_the_previous_cluster_chain() {
+        let image = build_fat12_image(&[], &[], &[]);
+        let mut fat12 = FAT12::from_bytes(image).expect("mount image");
+
+        // Repeated overwrites of the same file must not accumulate orphaned clusters:
+        // each write's old chain should be freed before (or as) a new one is allocated.
+        for i in 0u8..5 {
+            fat12.write_file(&name83("HELLO", "TXT"), &vec![b'a' + i; 3]).expect("write file");
+        }
+
+        let occupied = (2u16..40)
+            .filter(|&c| FAT12::<Cursor<Vec<u8>>>::get_fat12_entry(&fat12.fat, c) != 0x000)
+            .count();
+        assert_eq!(occupied, 1, "repeated overwrites must not leak clusters");
+
+        let entry = *fat12.search_file_by_name("HELLO.TXT").expect("find file again");
+        let data = fat12.read_file(&entry).expect("read overwritten file");
+        assert_eq!(data, vec![b'a' + 4; 3]);
+    }
+
+    #[test]
+    fn fat_error_display_messages_are_human_readable() {
+        assert_eq!(FatError::NotAFatFilesystem.to_string(), "not a FAT filesystem");
+        assert_eq!(FatError::FileNotFound.to_string(), "file not found");
+        assert_eq!(FatError::BrokenClusterChain { cluster: 7 }.to_string(), "broken cluster chain at cluster 7");
+        assert_eq!(FatError::DiskFull.to_string(), "not enough free space on disk");
+        assert_eq!(FatError::Unsupported("nope".to_string()).to_string(), "unsupported operation: nope");
+    }
+
+    #[test]
+    fn io_errors_convert_into_fat_error() {
+        let io_err = std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "eof");
+        let fat_err: FatError = io_err.into();
+        assert!(matches!(fat_err, FatError::Io(_)));
+    }
+
+    #[test]
+    fn decode_dos_date_unpacks_year_month_day() {
+        // 2024-03-15: year offset 44 (2024 - 1980), month 3, day 15.
+        let packed = (44u16 << 9) | (3u16 << 5) | 15u16;
+        assert_eq!(decode_dos_date(packed), (2024, 3, 15));
+    }
+
+    #[test]
+    fn decode_dos_time_unpacks_hour_minute_second() {
+        // 13:05:30: seconds/2 = 15, minute 5, hour 13.
+        let packed = (13u16 << 11) | (5u16 << 5) | 15u16;
+        assert_eq!(decode_dos_time(packed), (13, 5, 30));
+    }
+
+    #[test]
+    fn directory_entry_created_adds_tenths_of_a_second() {
+        let mut entry = short_entry(name83("FILE", "TXT"), 0x20, 0, 0);
+        entry.created_date = (44u16 << 9) | (3u16 << 5) | 15u16;
+        entry.created_tile = (13u16 << 11) | (5u16 << 5) | 15u16;
+        entry.created_time_tenths = 150; // +1.5s, truncated to +1 whole second.
+
+        let created = entry.created();
+        assert_eq!(created, DateTime { year: 2024, month: 3, day: 15, hour: 13, minute: 5, second: 31 });
+    }
+
+    #[test]
+    fn directory_entry_modified_has_no_tenths_component() {
+        let mut entry = short_entry(name83("FILE", "TXT"), 0x20, 0, 0);
+        entry.last_modification_date = (44u16 << 9) | (3u16 << 5) | 15u16;
+        entry.last_modification_time = (13u16 << 11) | (5u16 << 5) | 15u16;
+
+        let modified = entry.modified();
+        assert_eq!(modified, DateTime { year: 2024, month: 3, day: 15, hour: 13, minute: 5, second: 30 });
+    }
+
+    #[test]
+    fn read_file_truncates_to_the_directory_entrys_recorded_size() {
+        let image = build_fat12_image(&[], &[], &[]);
+        let mut fat12 = FAT12::from_bytes(image).expect("mount image");
+        fat12.write_file(&name83("SHORT", "TXT"), b"hi").expect("write short file");
+
+        let entry = *fat12.search_file_by_name("SHORT.TXT").expect("find file");
+        let data = fat12.read_file(&entry).expect("read file");
+        assert_eq!(data.len(), 2, "read_file must not return the whole cluster's padding");
+        assert_eq!(data, b"hi");
+    }
 }